@@ -2,19 +2,191 @@
 //! a recursive data structure using a JSON-path-like selector
 //! syntax.
 //!
-//! Also provides implementations for common types like [`serde_json::Value`].
-//!
-//! [`serde_json::Value`]: https://docs.serde.rs/serde_json/value/enum.Value.html
+//! Also provides implementations for common self-describing config
+//! formats, so the same selector syntax works uniformly across them.
 //!
 //! # Features
 //!
-//! Provides the following features:
+//! Provides the following features, each adding a `Dig` impl for a
+//! third-party value type:
+//!
+//! - `serde_json`: [`serde_json::Value`](https://docs.serde.rs/serde_json/value/enum.Value.html)
+//! - `toml`: [`toml::Value`](https://docs.rs/toml/latest/toml/enum.Value.html)
+//! - `serde_yaml`: [`serde_yaml::Value`](https://docs.rs/serde_yaml/latest/serde_yaml/enum.Value.html)
+//!
+//! # Limitations
+//!
+//! `Dig` is deliberately self-recursive: [`Dig::value_for_name`] and
+//! friends hand back `Option<&Self>`, which is what lets the same
+//! concrete type be dug into at every depth (as `serde_json::Value`
+//! does - an object's values are themselves `Value`s).  That shape
+//! doesn't admit a blanket impl for plain `HashMap<String, V>` /
+//! `BTreeMap<String, V>` containers whose values are some unrelated
+//! `V: Dig` type, since a lookup would need to hand back `&V`, not
+//! `&Self`.
 //!
-//! - `serde_json`: Include a `Dig` implementation for `serde_json::Value`
+//! No `Dig` impl is provided for `HashMap` or `BTreeMap`; supporting
+//! them would mean redesigning `Dig` around an associated child type
+//! instead of `Self`, which every method built on self-recursion
+//! ([`Dig::dig_all`], [`Dig::find_paths`],
+//! [`Dig::descendants_inclusive`], ...) depends on.  To dig into a
+//! map's values, look one up with the map's own `get`, then call
+//! [`Dig::dig`] on the resulting `V` directly.
+
+use std::borrow::Cow;
+use std::fmt;
 
 #[cfg(feature = "serde_json")]
 use serde_json::Value;
 
+/// A single parsed selector segment.
+///
+/// Selector strings are split into a sequence of these before
+/// being applied to a [Dig] implementation, so that container
+/// types which support indexed access (like arrays), wildcard
+/// expansion, and recursive descent can be addressed alongside
+/// plain keyed lookups (like objects).
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment<'a> {
+    /// A named key segment, e.g. `foo` in `foo.bar`.  A key that
+    /// would otherwise be ambiguous (containing a literal `.` or
+    /// `[`, or looking like a bare index) is written in
+    /// bracket-quoted form instead, e.g. `["foo.bar"]`, and
+    /// unescaped here (borrowed from the selector string unless it
+    /// contains escapes, in which case it is owned).
+    Name(Cow<'a, str>),
+
+    /// A numeric index segment, e.g. `0` in `foo.0` or `foo[0]`.
+    Index(usize),
+
+    /// A `*` segment, matching every child of the candidates at
+    /// that position.
+    Wildcard,
+
+    /// A `..` segment, matching the candidates at that position
+    /// together with all of their transitive descendants.
+    RecursiveDescent,
+}
+
+impl<'a> Segment<'a> {
+    /// Parses a full selector string into an ordered list of
+    /// segments, dropping an optional leading `$` root sigil,
+    /// reporting malformed brackets or indices as a
+    /// [`SelectorError`] instead of silently falling back to a
+    /// best-effort interpretation.
+    ///
+    /// A run of two dots (`..`) is recognized as a single
+    /// [`Segment::RecursiveDescent`] rather than two separator
+    /// characters.  A segment may be written as a bare name (`foo`),
+    /// as a bracketed numeric index (`[0]`), or as a bracket-quoted
+    /// string (`["foo.bar"]`) for keys that would otherwise be
+    /// ambiguous.  This is the single parser used by [`Dig::dig`],
+    /// [`Dig::dig_all`], [`Dig::dig_mut`] and [`Selector::compile`],
+    /// so the selector dialect doesn't diverge between entry
+    /// points.
+    fn parse_selector(selector: &'a str) -> Result<Vec<Segment<'a>>, SelectorError> {
+        let mut rest = selector;
+        let mut segments = Vec::new();
+
+        if let Some(stripped) = rest.strip_prefix('$') {
+            rest = stripped;
+        }
+
+        while !rest.is_empty() {
+            if let Some(stripped) = rest.strip_prefix("..") {
+                segments.push(Segment::RecursiveDescent);
+                rest = stripped;
+                continue;
+            }
+            if let Some(stripped) = rest.strip_prefix('.') {
+                rest = stripped;
+                continue;
+            }
+            if let Some(stripped) = rest.strip_prefix('*') {
+                segments.push(Segment::Wildcard);
+                rest = stripped;
+                continue;
+            }
+            if let Some(stripped) = rest.strip_prefix('[') {
+                let (segment, remainder) = parse_bracket(stripped)?;
+                segments.push(segment);
+                rest = remainder;
+                continue;
+            }
+
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            let (part, remainder) = rest.split_at(end);
+            rest = remainder;
+
+            if part.contains(']') {
+                return Err(SelectorError::UnbalancedBracket(part.to_string()));
+            }
+
+            segments.push(match part.parse::<usize>() {
+                Ok(index) => Segment::Index(index),
+                Err(_) => Segment::Name(Cow::Borrowed(part)),
+            });
+        }
+
+        Ok(segments)
+    }
+}
+
+/// Parses the contents of a `[...]` selector segment (the slice
+/// just past the opening `[`), recognizing either a bracket-quoted
+/// string (`"foo.bar"]`) or a numeric index (`0]`).
+fn parse_bracket(rest: &str) -> Result<(Segment<'_>, &str), SelectorError> {
+    if let Some(after_quote) = rest.strip_prefix('"') {
+        let (content, remainder) = parse_quoted(after_quote)?;
+        let remainder = remainder
+            .strip_prefix(']')
+            .ok_or_else(|| SelectorError::UnbalancedBracket(format!("[{rest}")))?;
+        Ok((Segment::Name(content), remainder))
+    } else {
+        let end = rest
+            .find(']')
+            .ok_or_else(|| SelectorError::UnbalancedBracket(format!("[{rest}")))?;
+        let index_str = &rest[..end];
+        let index = index_str
+            .parse::<usize>()
+            .map_err(|_| SelectorError::InvalidIndex(format!("[{index_str}]")))?;
+        Ok((Segment::Index(index), &rest[end + 1..]))
+    }
+}
+
+/// Parses a bracket-quoted string segment's contents (the slice
+/// just past the opening `"`), unescaping `\"` and `\\`, and
+/// returns it together with whatever follows the closing `"`.
+///
+/// Mirrors the escaping [`render_path`] applies, so every rendered
+/// path parses back to the key it was rendered from.
+fn parse_quoted(rest: &str) -> Result<(Cow<'_, str>, &str), SelectorError> {
+    if !rest.contains('\\') {
+        return match rest.find('"') {
+            Some(end) => Ok((Cow::Borrowed(&rest[..end]), &rest[end + 1..])),
+            None => Err(SelectorError::UnbalancedBracket(format!("[\"{rest}"))),
+        };
+    }
+
+    let mut unescaped = String::new();
+    let mut chars = rest.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((Cow::Owned(unescaped), &rest[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, '"')) => unescaped.push('"'),
+                Some((_, '\\')) => unescaped.push('\\'),
+                Some((_, other)) => unescaped.push(other),
+                None => return Err(SelectorError::UnbalancedBracket(format!("[\"{rest}"))),
+            },
+            other => unescaped.push(other),
+        }
+    }
+
+    Err(SelectorError::UnbalancedBracket(format!("[\"{rest}")))
+}
+
 /// Used to "dig through" recursive data structures to extract
 /// named values using a selector string.  Selectors are sequential
 /// names separated by an ASCII '.' character, optionally prefixed
@@ -51,30 +223,393 @@ pub trait Dig {
     /// or none.
     fn value_for_name(&self, name: &str) -> Option<&Self>;
 
+    /// Retrieves a datum at the given array index, or none.
+    ///
+    /// The default implementation always returns [None], so
+    /// implementors that don't support indexed access (e.g. plain
+    /// maps) need not do anything special.
+    fn value_for_index(&self, _index: usize) -> Option<&Self> {
+        None
+    }
+
+    /// Retrieves a mutable reference to the datum identified by the
+    /// given name segment, or none.  The mutable counterpart to
+    /// [`Dig::value_for_name`].
+    fn value_for_name_mut(&mut self, name: &str) -> Option<&mut Self>;
+
+    /// Retrieves a mutable reference to the datum at the given
+    /// array index, or none.  The mutable counterpart to
+    /// [`Dig::value_for_index`].
+    ///
+    /// The default implementation always returns [None], so
+    /// implementors that don't support indexed access need not do
+    /// anything special.
+    fn value_for_index_mut(&mut self, _index: usize) -> Option<&mut Self> {
+        None
+    }
+
+    /// Returns every immediate child of `self` together with the
+    /// [`PathPart`] that reaches it - an object's fields, or an
+    /// array's elements.  Array elements come in index order; object
+    /// fields come in the iteration order of the underlying map,
+    /// which for a sorted map is key order rather than the order the
+    /// keys appeared in the source document.
+    ///
+    /// The default implementation returns an empty vector, so
+    /// implementors that aren't containers need not do anything
+    /// special.  This is the single primitive that [`Dig::children`]
+    /// and [`Dig::find_paths`] are built on.
+    fn entries(&self) -> Vec<(PathPart, &Self)> {
+        Vec::new()
+    }
+
+    /// Returns every immediate child of `self` - the values of an
+    /// object, or the elements of an array - in the order
+    /// [`Dig::entries`] yields them.
+    ///
+    /// This has a default implementation in terms of [`Dig::entries`]
+    /// and should not normally need to be overridden.  Used by
+    /// [`Dig::dig_all`] to implement `*` and `..` selector segments.
+    fn children(&self) -> Vec<&Self> {
+        self.entries().into_iter().map(|(_, child)| child).collect()
+    }
+
+    /// Returns `self` together with all of its transitive
+    /// descendants, in pre-order (`self` first, then each child's
+    /// own `descendants_inclusive`, children taken in the order
+    /// [`Dig::children`] yields them).
+    ///
+    /// This has a default implementation in terms of [`Dig::children`]
+    /// and should not normally need to be overridden.
+    fn descendants_inclusive(&self) -> Vec<&Self> {
+        let mut result = vec![self];
+        for child in self.children() {
+            result.extend(child.descendants_inclusive());
+        }
+        result
+    }
+
     /// Fetches the data within [self] identified by the given
     /// `selector`.
     ///
     /// Selector strings have a lightweight syntax resembling basic
-    /// JSON-Path selectors - chains of name segments, separated by
-    /// ASCII period characters (`.`).  As in JSON Path, selectors
-    /// can be absolute (i.e. prefixed with a sigil, like `$.`) or
-    /// relative.
+    /// JSON-Path selectors - chains of segments separated by ASCII
+    /// period characters (`.`).  As in JSON Path, selectors can be
+    /// absolute (i.e. prefixed with a sigil, like `$.`) or relative.
+    ///
+    /// A segment is ordinarily a name, used to look up a value in a
+    /// keyed container.  It may also be - or end with - a bracketed
+    /// integer index, used to look up a value in an indexed
+    /// container, e.g. `phones[0]`.  A bare integer segment, e.g.
+    /// `phones.0`, is likewise treated as an index.  A name that
+    /// would otherwise be ambiguous (containing a literal `.` or
+    /// `[`, or looking like a bare index) can be written in
+    /// bracket-quoted form instead, e.g. `["123"]` or
+    /// `["weird.key"]`; this is the form [`Dig::find_paths`] renders
+    /// such keys in.
     ///
     /// Returns an optional result, containing a reference to the named
-    /// data if found, and none if not.
+    /// data if found, and none if not.  Equivalent to taking the
+    /// first element of [`Dig::dig_all`].
+    ///
+    /// Internally this compiles `selector` via [`Selector::compile`]
+    /// on every call; for repeated lookups with the same selector,
+    /// compile it once and reuse it with [`Dig::dig_compiled`]
+    /// instead.
     fn dig(&self, selector: impl AsRef<str>) -> Option<&Self> {
-        selector
-            .as_ref()
-            .split('.')
-            .skip_while(|&s| s == "$")
-            .filter(|&s| !s.is_empty())
-            .fold(Some(self), |res, name| match res {
-                Some(d) => d.value_for_name(name),
-                None => None,
-            })
+        let selector = Selector::compile(selector.as_ref()).ok()?;
+        self.dig_compiled(&selector)
+    }
+
+    /// Fetches every datum within [self] matched by the given
+    /// `selector`, with children visited in the order
+    /// [`Dig::entries`] yields them.
+    ///
+    /// In addition to the name and index segments supported by
+    /// [`Dig::dig`], selectors passed to `dig_all` may contain two
+    /// JSON-Path-style operators:
+    ///
+    /// - `*`, a wildcard matching every child of the candidates at
+    ///   that position (e.g. `foo.*` matches every value of the
+    ///   `foo` object, or every element of the `foo` array).
+    /// - `..`, recursive descent, matching the candidates at that
+    ///   position together with all of their transitive descendants
+    ///   (e.g. `foo..bar` matches `bar` nested at any depth under
+    ///   `foo`).
+    fn dig_all(&self, selector: impl AsRef<str>) -> Vec<&Self> {
+        match Segment::parse_selector(selector.as_ref()) {
+            Ok(segments) => run_segments(self, segments),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Like [`Dig::dig`], but takes an already-[`Selector::compile`]d
+    /// selector, skipping the per-call parsing [`Dig::dig`] and
+    /// [`Dig::dig_all`] otherwise do.
+    fn dig_compiled(&self, selector: &Selector) -> Option<&Self> {
+        run_segments(self, selector.segments()).into_iter().next()
+    }
+
+    /// Like [`Dig::dig`], but navigates to the selected node
+    /// mutably, allowing callers to update a nested value in place
+    /// (e.g. `foo.bar.baz`) without manually indexing each level.
+    ///
+    /// Only name and index segments are supported - `*` and `..`
+    /// would require handing out more than one mutable reference at
+    /// once, which isn't possible, so a selector containing either
+    /// always resolves to [None].
+    fn dig_mut(&mut self, selector: impl AsRef<str>) -> Option<&mut Self> {
+        let segments = Segment::parse_selector(selector.as_ref()).ok()?;
+
+        let mut current = self;
+        for segment in segments {
+            current = match segment {
+                Segment::Name(name) => current.value_for_name_mut(name.as_ref())?,
+                Segment::Index(index) => current.value_for_index_mut(index)?,
+                Segment::Wildcard | Segment::RecursiveDescent => return None,
+            };
+        }
+
+        Some(current)
+    }
+
+    /// Finds every selector string whose [`Dig::dig`] would resolve
+    /// to a node equal to `needle`, searching `self` depth-first.
+    ///
+    /// Each returned selector is rooted at `$`, e.g. `$.foo.bar[0]`,
+    /// and round-trips back through [`Dig::dig`] / [`Dig::dig_all`].
+    /// A subtree that matches `needle` is not searched further,
+    /// since a node can't both equal the needle and contain another
+    /// occurrence of it.
+    fn find_paths(&self, needle: &Self) -> Vec<String>
+    where
+        Self: PartialEq,
+    {
+        let mut paths = Vec::new();
+        let mut stack = Vec::new();
+        find_paths_into(self, needle, &mut stack, &mut paths);
+        paths
+    }
+}
+
+/// Walks `start` through `segments` one at a time, threading a
+/// worklist of current candidates, and shared by [`Dig::dig_all`]
+/// and [`Dig::dig_compiled`].
+fn run_segments<'t, 's, T: Dig + ?Sized>(
+    start: &'t T,
+    segments: impl IntoIterator<Item = Segment<'s>>,
+) -> Vec<&'t T> {
+    let mut candidates = vec![start];
+
+    for segment in segments {
+        candidates = match segment {
+            Segment::Name(ref name) => candidates
+                .into_iter()
+                .filter_map(|c| c.value_for_name(name.as_ref()))
+                .collect(),
+            Segment::Index(index) => candidates
+                .into_iter()
+                .filter_map(|c| c.value_for_index(index))
+                .collect(),
+            Segment::Wildcard => candidates.into_iter().flat_map(|c| c.children()).collect(),
+            Segment::RecursiveDescent => candidates
+                .into_iter()
+                .flat_map(|c| c.descendants_inclusive())
+                .collect(),
+        };
+
+        if candidates.is_empty() {
+            break;
+        }
+    }
+
+    candidates
+}
+
+/// Recursive helper behind [`Dig::find_paths`], tracking the current
+/// path as a stack of [`PathPart`]s.
+fn find_paths_into<T: Dig + PartialEq + ?Sized>(
+    node: &T,
+    needle: &T,
+    stack: &mut Vec<PathPart>,
+    paths: &mut Vec<String>,
+) {
+    if node == needle {
+        paths.push(render_path(stack));
+        return;
+    }
+
+    for (part, child) in node.entries() {
+        stack.push(part);
+        find_paths_into(child, needle, stack, paths);
+        stack.pop();
+    }
+}
+
+/// Identifies how a child was reached from its parent, as returned
+/// by [`Dig::entries`] and rendered by [`Dig::find_paths`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathPart {
+    /// An object field, reached by key.
+    Field(String),
+
+    /// An array element, reached by position.
+    Index(usize),
+}
+
+/// Renders a stack of [`PathPart`]s into a `$`-rooted selector
+/// string that [`Dig::dig`] / [`Dig::dig_all`] can parse back.
+///
+/// Identifier-safe keys (ASCII alphanumeric plus `_`) are rendered
+/// as `.key`; all other keys are rendered in bracket-quoted form,
+/// `["weird.key"]`, to avoid colliding with the `.` separator.
+fn render_path(stack: &[PathPart]) -> String {
+    let mut path = String::from("$");
+
+    for part in stack {
+        match part {
+            PathPart::Field(key) if is_identifier_safe(key) => {
+                path.push('.');
+                path.push_str(key);
+            }
+            PathPart::Field(key) => {
+                path.push_str("[\"");
+                path.push_str(&key.replace('\\', "\\\\").replace('"', "\\\""));
+                path.push_str("\"]");
+            }
+            PathPart::Index(index) => {
+                path.push('[');
+                path.push_str(&index.to_string());
+                path.push(']');
+            }
+        }
+    }
+
+    path
+}
+
+/// Whether `key` can be rendered as a bare `.key` selector segment
+/// rather than needing bracket-quoted form.
+///
+/// A purely-numeric key (e.g. `"123"`) is deliberately excluded even
+/// though every character is alphanumeric: a bare `.123` segment
+/// parses back as [`Segment::Index`], not a name lookup, so such
+/// keys must go through the bracket-quoted form to round-trip.
+fn is_identifier_safe(key: &str) -> bool {
+    !key.is_empty()
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !key.chars().all(|c| c.is_ascii_digit())
+}
+
+/// A selector string parsed once into a validated, reusable list of
+/// segments.
+///
+/// Parsing and validating a selector is wasted work when the same
+/// selector is applied to many documents in a hot loop; a `Selector`
+/// does that work once, up front, and can then be replayed against
+/// any number of values with [`Dig::dig_compiled`].
+///
+#[cfg_attr(
+    feature = "serde_json",
+    doc = r##"
+```
+# use digger::{Dig, Selector};
+# use serde_json::json;
+let selector = Selector::compile("foo.bar").unwrap();
+
+let a = json!({ "foo": { "bar": 1 } });
+let b = json!({ "foo": { "bar": 2 } });
+
+assert_eq!(a.dig_compiled(&selector), Some(&json!(1)));
+assert_eq!(b.dig_compiled(&selector), Some(&json!(2)));
+```
+"##
+)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    segments: Vec<CompiledSegment>,
+}
+
+impl Selector {
+    /// Parses `selector` into a reusable `Selector`, reporting
+    /// malformed bracketed indices as a [`SelectorError`] rather
+    /// than silently falling back to a best-effort interpretation.
+    pub fn compile(selector: impl AsRef<str>) -> Result<Selector, SelectorError> {
+        let segments = Segment::parse_selector(selector.as_ref())?
+            .into_iter()
+            .map(CompiledSegment::from)
+            .collect();
+
+        Ok(Selector { segments })
+    }
+
+    /// Borrows the compiled segments as [`Segment`]s, for use by
+    /// [`run_segments`].
+    fn segments(&self) -> impl Iterator<Item = Segment<'_>> {
+        self.segments.iter().map(CompiledSegment::as_segment)
+    }
+}
+
+/// An owned, `'static` counterpart to [`Segment`], used by
+/// [`Selector`] to outlive the selector string it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CompiledSegment {
+    Name(String),
+    Index(usize),
+    Wildcard,
+    RecursiveDescent,
+}
+
+impl CompiledSegment {
+    fn as_segment(&self) -> Segment<'_> {
+        match self {
+            CompiledSegment::Name(name) => Segment::Name(Cow::Borrowed(name.as_str())),
+            CompiledSegment::Index(index) => Segment::Index(*index),
+            CompiledSegment::Wildcard => Segment::Wildcard,
+            CompiledSegment::RecursiveDescent => Segment::RecursiveDescent,
+        }
+    }
+}
+
+impl<'a> From<Segment<'a>> for CompiledSegment {
+    fn from(segment: Segment<'a>) -> Self {
+        match segment {
+            Segment::Name(name) => CompiledSegment::Name(name.into_owned()),
+            Segment::Index(index) => CompiledSegment::Index(index),
+            Segment::Wildcard => CompiledSegment::Wildcard,
+            Segment::RecursiveDescent => CompiledSegment::RecursiveDescent,
+        }
     }
 }
 
+/// An error produced when compiling a selector string into a
+/// [`Selector`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectorError {
+    /// A selector segment has mismatched `[`/`]` brackets, e.g.
+    /// `foo[0` or `foo]`.
+    UnbalancedBracket(String),
+
+    /// A selector segment has a bracketed index that isn't a valid
+    /// non-negative integer, e.g. `foo[bar]`.
+    InvalidIndex(String),
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectorError::UnbalancedBracket(part) => {
+                write!(f, "unbalanced brackets in selector segment {part:?}")
+            }
+            SelectorError::InvalidIndex(part) => {
+                write!(f, "invalid index in selector segment {part:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
 #[cfg(feature = "serde_json")]
 impl Dig for Value {
     fn value_for_name(&self, name: &str) -> Option<&Self> {
@@ -83,6 +618,141 @@ impl Dig for Value {
             _ => None,
         }
     }
+
+    fn value_for_index(&self, index: usize) -> Option<&Self> {
+        match self {
+            Value::Array(a) => a.get(index),
+            _ => None,
+        }
+    }
+
+    fn value_for_name_mut(&mut self, name: &str) -> Option<&mut Self> {
+        match self {
+            Value::Object(o) => o.get_mut(name),
+            _ => None,
+        }
+    }
+
+    fn value_for_index_mut(&mut self, index: usize) -> Option<&mut Self> {
+        match self {
+            Value::Array(a) => a.get_mut(index),
+            _ => None,
+        }
+    }
+
+    fn entries(&self) -> Vec<(PathPart, &Self)> {
+        match self {
+            Value::Object(o) => o
+                .iter()
+                .map(|(k, v)| (PathPart::Field(k.clone()), v))
+                .collect(),
+            Value::Array(a) => a
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (PathPart::Index(i), v))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "toml")]
+impl Dig for toml::Value {
+    fn value_for_name(&self, name: &str) -> Option<&Self> {
+        match self {
+            toml::Value::Table(t) => t.get(name),
+            _ => None,
+        }
+    }
+
+    fn value_for_index(&self, index: usize) -> Option<&Self> {
+        match self {
+            toml::Value::Array(a) => a.get(index),
+            _ => None,
+        }
+    }
+
+    fn value_for_name_mut(&mut self, name: &str) -> Option<&mut Self> {
+        match self {
+            toml::Value::Table(t) => t.get_mut(name),
+            _ => None,
+        }
+    }
+
+    fn value_for_index_mut(&mut self, index: usize) -> Option<&mut Self> {
+        match self {
+            toml::Value::Array(a) => a.get_mut(index),
+            _ => None,
+        }
+    }
+
+    fn entries(&self) -> Vec<(PathPart, &Self)> {
+        match self {
+            toml::Value::Table(t) => t
+                .iter()
+                .map(|(k, v)| (PathPart::Field(k.clone()), v))
+                .collect(),
+            toml::Value::Array(a) => a
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (PathPart::Index(i), v))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "serde_yaml")]
+impl Dig for serde_yaml::Value {
+    fn value_for_name(&self, name: &str) -> Option<&Self> {
+        match self {
+            serde_yaml::Value::Mapping(m) => m.get(serde_yaml::Value::String(name.to_string())),
+            _ => None,
+        }
+    }
+
+    fn value_for_index(&self, index: usize) -> Option<&Self> {
+        match self {
+            serde_yaml::Value::Sequence(s) => s.get(index),
+            _ => None,
+        }
+    }
+
+    fn value_for_name_mut(&mut self, name: &str) -> Option<&mut Self> {
+        match self {
+            serde_yaml::Value::Mapping(m) => {
+                m.get_mut(serde_yaml::Value::String(name.to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    fn value_for_index_mut(&mut self, index: usize) -> Option<&mut Self> {
+        match self {
+            serde_yaml::Value::Sequence(s) => s.get_mut(index),
+            _ => None,
+        }
+    }
+
+    fn entries(&self) -> Vec<(PathPart, &Self)> {
+        match self {
+            // Non-string keys (integer, bool, null, ... - all legal
+            // in YAML) are skipped rather than coerced to a string:
+            // value_for_name/dig can only ever reach string keys, so
+            // a coerced entry would produce a find_paths result that
+            // doesn't round-trip back through dig.
+            serde_yaml::Value::Mapping(m) => m
+                .iter()
+                .filter_map(|(k, v)| {
+                    k.as_str().map(|key| (PathPart::Field(key.to_string()), v))
+                })
+                .collect(),
+            serde_yaml::Value::Sequence(s) => {
+                s.iter().enumerate().map(|(i, v)| (PathPart::Index(i), v)).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -90,7 +760,7 @@ impl Dig for Value {
 mod json_tests {
     use serde_json::{json, Value};
 
-    use super::Dig;
+    use super::{Dig, Selector, SelectorError};
 
     #[test]
     fn not_found_at_end() {
@@ -167,4 +837,432 @@ mod json_tests {
 
         assert_eq!(Some(&expected), result);
     }
+
+    #[test]
+    fn bracketed_index_digs_into_array() {
+        let value = json!({
+            "foo": {
+                "phones": ["555-1234", "555-5678"]
+            }
+        });
+
+        let result = value.dig("foo.phones[0]");
+        let expected = Value::String(String::from("555-1234"));
+
+        assert_eq!(Some(&expected), result);
+    }
+
+    #[test]
+    fn bare_index_digs_into_array() {
+        let value = json!({
+            "foo": {
+                "phones": ["555-1234", "555-5678"]
+            }
+        });
+
+        let result = value.dig("foo.phones.1");
+        let expected = Value::String(String::from("555-5678"));
+
+        assert_eq!(Some(&expected), result);
+    }
+
+    #[test]
+    fn index_out_of_bounds_is_none() {
+        let value = json!({
+            "foo": {
+                "phones": ["555-1234"]
+            }
+        });
+
+        let result = value.dig("foo.phones[5]");
+
+        assert_eq!(None, result);
+    }
+
+    #[test]
+    fn wildcard_matches_every_object_value() {
+        let value = json!({
+            "foo": {
+                "bar": 1,
+                "baz": 2
+            }
+        });
+
+        let mut result: Vec<&Value> = value.dig_all("foo.*");
+        result.sort_by_key(|v| v.as_i64());
+
+        assert_eq!(vec![&json!(1), &json!(2)], result);
+    }
+
+    #[test]
+    fn wildcard_matches_every_array_element() {
+        let value = json!({
+            "foo": ["a", "b", "c"]
+        });
+
+        let result = value.dig_all("foo.*");
+
+        assert_eq!(vec![&json!("a"), &json!("b"), &json!("c")], result);
+    }
+
+    #[test]
+    fn recursive_descent_finds_nested_names_at_any_depth() {
+        let value = json!({
+            "foo": {
+                "bar": {
+                    "baz": 1
+                },
+                "baz": 2
+            }
+        });
+
+        let mut result: Vec<&Value> = value.dig_all("foo..baz");
+        result.sort_by_key(|v| v.as_i64());
+
+        assert_eq!(vec![&json!(1), &json!(2)], result);
+    }
+
+    #[test]
+    fn dig_returns_first_of_dig_all() {
+        let value = json!({
+            "foo": ["a", "b"]
+        });
+
+        let result = value.dig("foo.*");
+
+        assert_eq!(Some(&json!("a")), result);
+    }
+
+    #[test]
+    fn find_paths_locates_simple_nested_field() {
+        let value = json!({
+            "foo": {
+                "bar": {
+                    "baz": "hello there"
+                }
+            }
+        });
+
+        let needle = json!("hello there");
+        let paths = value.find_paths(&needle);
+
+        assert_eq!(vec!["$.foo.bar.baz".to_string()], paths);
+    }
+
+    #[test]
+    fn find_paths_locates_array_elements() {
+        let value = json!({
+            "foo": {
+                "phones": ["555-1234", "555-5678"]
+            }
+        });
+
+        let needle = json!("555-5678");
+        let paths = value.find_paths(&needle);
+
+        assert_eq!(vec!["$.foo.phones[1]".to_string()], paths);
+    }
+
+    #[test]
+    fn find_paths_returns_every_occurrence() {
+        let value = json!({
+            "foo": { "baz": 1 },
+            "bar": { "baz": 1 }
+        });
+
+        let needle = json!(1);
+        let mut paths = value.find_paths(&needle);
+        paths.sort();
+
+        assert_eq!(vec!["$.bar.baz".to_string(), "$.foo.baz".to_string()], paths);
+    }
+
+    #[test]
+    fn find_paths_quotes_non_identifier_keys() {
+        let value = json!({
+            "weird.key": "found me"
+        });
+
+        let needle = json!("found me");
+        let paths = value.find_paths(&needle);
+
+        assert_eq!(vec!["$[\"weird.key\"]".to_string()], paths);
+    }
+
+    #[test]
+    fn find_paths_does_not_descend_into_a_match() {
+        let value = json!({
+            "foo": {
+                "bar": "baz"
+            }
+        });
+
+        let needle = value["foo"].clone();
+        let paths = value.find_paths(&needle);
+
+        assert_eq!(vec!["$.foo".to_string()], paths);
+    }
+
+    #[test]
+    fn find_paths_returns_root_for_whole_document_match() {
+        let value = json!({ "foo": "bar" });
+        let needle = value.clone();
+
+        let paths = value.find_paths(&needle);
+
+        assert_eq!(vec!["$".to_string()], paths);
+    }
+
+    #[test]
+    fn round_trip_found_paths_resolve_via_dig() {
+        let value = json!({
+            "foo": {
+                "phones": ["555-1234", "555-5678"]
+            }
+        });
+
+        let needle = json!("555-5678");
+        let paths = value.find_paths(&needle);
+
+        for path in paths {
+            assert_eq!(Some(&needle), value.dig(path));
+        }
+    }
+
+    #[test]
+    fn round_trip_quoted_key_resolves_via_dig() {
+        let value = json!({ "api-key": "secret" });
+
+        let needle = json!("secret");
+        let paths = value.find_paths(&needle);
+
+        assert_eq!(vec!["$[\"api-key\"]".to_string()], paths);
+        for path in paths {
+            assert_eq!(Some(&needle), value.dig(path));
+        }
+    }
+
+    #[test]
+    fn round_trip_numeric_key_resolves_via_dig() {
+        let value = json!({ "123": "v" });
+
+        let needle = json!("v");
+        let paths = value.find_paths(&needle);
+
+        assert_eq!(vec!["$[\"123\"]".to_string()], paths);
+        for path in paths {
+            assert_eq!(Some(&needle), value.dig(path));
+        }
+    }
+
+    #[test]
+    fn quoted_segment_digs_into_literal_key_containing_brackets() {
+        let value = json!({ "foo[bar]": "literal" });
+
+        let result = value.dig(r#"["foo[bar]"]"#);
+
+        assert_eq!(Some(&json!("literal")), result);
+    }
+
+    #[test]
+    fn compiled_selector_digs_like_dig() {
+        let value = json!({
+            "foo": {
+                "bar": {
+                    "baz": "hello there"
+                }
+            }
+        });
+
+        let selector = Selector::compile("foo.bar.baz").unwrap();
+        let expected = Value::String(String::from("hello there"));
+
+        assert_eq!(Some(&expected), value.dig_compiled(&selector));
+    }
+
+    #[test]
+    fn compiled_selector_reused_across_documents() {
+        let selector = Selector::compile("foo.bar").unwrap();
+
+        let a = json!({ "foo": { "bar": 1 } });
+        let b = json!({ "foo": { "bar": 2 } });
+
+        assert_eq!(Some(&json!(1)), a.dig_compiled(&selector));
+        assert_eq!(Some(&json!(2)), b.dig_compiled(&selector));
+    }
+
+    #[test]
+    fn compile_rejects_unbalanced_bracket() {
+        let result = Selector::compile("foo[0");
+
+        assert_eq!(
+            Err(SelectorError::UnbalancedBracket(String::from("[0"))),
+            result
+        );
+    }
+
+    #[test]
+    fn compile_rejects_non_numeric_index() {
+        let result = Selector::compile("foo[bar]");
+
+        assert_eq!(
+            Err(SelectorError::InvalidIndex(String::from("[bar]"))),
+            result
+        );
+    }
+
+    #[test]
+    fn dig_returns_none_for_malformed_selector() {
+        let value = json!({ "foo": { "bar": 1 } });
+
+        assert_eq!(None, value.dig("foo[bar]"));
+    }
+
+    #[test]
+    fn dig_all_returns_empty_for_malformed_selector() {
+        let value = json!({ "foo": { "bar": 1 } });
+
+        assert_eq!(Vec::<&Value>::new(), value.dig_all("foo[bar]"));
+    }
+
+    #[test]
+    fn malformed_bracket_does_not_fall_back_to_digging_through_the_name() {
+        // A document with a genuine key named "foo[bar]" nested under
+        // "foo" must not be matched by the selector "foo[bar]" - that
+        // selector is a malformed index on "foo" and should be
+        // rejected outright, not silently reinterpreted as digging
+        // into "foo" and then looking up the literal key "foo[bar]".
+        let value = json!({
+            "foo": {
+                "foo[bar]": "should not be reachable this way"
+            }
+        });
+
+        assert_eq!(None, value.dig("foo[bar]"));
+        assert_eq!(Vec::<&Value>::new(), value.dig_all("foo[bar]"));
+    }
+
+    #[test]
+    fn dig_mut_updates_nested_value_in_place() {
+        let mut value = json!({
+            "foo": {
+                "bar": {
+                    "baz": 1
+                }
+            }
+        });
+
+        if let Some(baz) = value.dig_mut("foo.bar.baz") {
+            *baz = json!(2);
+        }
+
+        assert_eq!(Some(&json!(2)), value.dig("foo.bar.baz"));
+    }
+
+    #[test]
+    fn dig_mut_updates_array_element_in_place() {
+        let mut value = json!({
+            "foo": {
+                "phones": ["555-1234", "555-5678"]
+            }
+        });
+
+        if let Some(phone) = value.dig_mut("foo.phones[1]") {
+            *phone = json!("555-0000");
+        }
+
+        assert_eq!(Some(&json!("555-0000")), value.dig("foo.phones[1]"));
+    }
+
+    #[test]
+    fn dig_mut_is_none_when_a_segment_does_not_resolve() {
+        let mut value = json!({
+            "foo": {
+                "bar": 1
+            }
+        });
+
+        assert_eq!(None, value.dig_mut("foo.quux"));
+    }
+
+    #[test]
+    fn dig_mut_is_none_for_wildcard_selector() {
+        let mut value = json!({ "foo": { "bar": 1 } });
+
+        assert_eq!(None, value.dig_mut("foo.*"));
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "toml")]
+mod toml_tests {
+    use super::Dig;
+
+    #[test]
+    fn digs_into_nested_table() {
+        let value: toml::Value = "[foo.bar]\nbaz = \"hello there\"\n".parse().unwrap();
+
+        let result = value.dig("foo.bar.baz");
+
+        assert_eq!(Some(&toml::Value::String(String::from("hello there"))), result);
+    }
+
+    #[test]
+    fn digs_into_array_element() {
+        let value: toml::Value = "phones = [\"555-1234\", \"555-5678\"]\n".parse().unwrap();
+
+        let result = value.dig("phones[1]");
+
+        assert_eq!(Some(&toml::Value::String(String::from("555-5678"))), result);
+    }
+
+    #[test]
+    fn not_found_is_none() {
+        let value: toml::Value = "[foo]\nbar = 1\n".parse().unwrap();
+
+        assert_eq!(None, value.dig("foo.quux"));
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "serde_yaml")]
+mod yaml_tests {
+    use super::Dig;
+
+    #[test]
+    fn digs_into_nested_mapping() {
+        let value: serde_yaml::Value = serde_yaml::from_str("foo:\n  bar:\n    baz: hello there\n").unwrap();
+
+        let result = value.dig("foo.bar.baz");
+
+        assert_eq!(Some(&serde_yaml::Value::String(String::from("hello there"))), result);
+    }
+
+    #[test]
+    fn digs_into_sequence_element() {
+        let value: serde_yaml::Value =
+            serde_yaml::from_str("phones:\n  - \"555-1234\"\n  - \"555-5678\"\n").unwrap();
+
+        let result = value.dig("phones[1]");
+
+        assert_eq!(Some(&serde_yaml::Value::String(String::from("555-5678"))), result);
+    }
+
+    #[test]
+    fn not_found_is_none() {
+        let value: serde_yaml::Value = serde_yaml::from_str("foo:\n  bar: 1\n").unwrap();
+
+        assert_eq!(None, value.dig("foo.quux"));
+    }
+
+    #[test]
+    fn non_string_keys_are_skipped_rather_than_collapsed() {
+        let value: serde_yaml::Value = serde_yaml::from_str("1: foo\n2: bar\n").unwrap();
+
+        let needle = serde_yaml::Value::String(String::from("bar"));
+        let paths = value.find_paths(&needle);
+
+        assert_eq!(Vec::<String>::new(), paths);
+        assert_eq!(None, value.dig("[\"\"]"));
+    }
 }